@@ -1,15 +1,21 @@
-use std::{f64::consts::PI, sync::Arc, time::Duration};
+use std::{f64::consts::PI, path::PathBuf, sync::Arc, time::Duration};
 
 use bevy::{
+    app::AppExit,
     core::FrameCount,
     diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin},
+    ecs::system::SystemParam,
+    input::gamepad::{GamepadAxisType, GamepadButtonType, GamepadConnectionEvent},
+    math::EulerRot,
     pbr::DirectionalLightShadowMap,
-    prelude::{IntoSystem, *},
+    prelude::*,
+    render::mesh::PrimitiveTopology,
 };
 use bevy_egui::{
     egui::{self, DragValue, Ui},
     EguiContexts, EguiSettings,
 };
+use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 use strum::IntoEnumIterator;
 
@@ -19,10 +25,28 @@ impl Into<CtrlId> for usize {
     }
 }
 
-#[derive(Hash, Clone, PartialEq, Eq, Debug)]
+/// Serializes a `Mat4` as its 16 column-major floats, since `glam` types
+/// don't derive `serde` traits themselves.
+mod mat4_serde {
+    use bevy::prelude::Mat4;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(mat: &Mat4, serializer: S) -> Result<S::Ok, S::Error> {
+        mat.to_cols_array().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Mat4, D::Error> {
+        let cols = <[f32; 16]>::deserialize(deserializer)?;
+        Ok(Mat4::from_cols_array(&cols))
+    }
+}
+
+#[derive(Hash, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 struct CtrlId(usize);
 
-#[derive(Clone, Default, Debug, EnumIter, Eq, PartialEq, strum::Display)]
+#[derive(
+    Clone, Default, Debug, EnumIter, Eq, PartialEq, strum::Display, Serialize, Deserialize,
+)]
 enum CtrlMode {
     #[default]
     Normal,
@@ -81,7 +105,7 @@ impl CtrlMode {
     // }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct CtrlState {
     is_changed: bool,
     mode: CtrlMode,
@@ -97,7 +121,7 @@ impl CtrlState {
 
 const FOUR_PI: f64 = PI * 4.;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 struct CtrlsState(std::collections::HashMap<CtrlId, CtrlState>);
 
 impl CtrlsState {
@@ -116,32 +140,90 @@ impl CtrlsState {
 
 // This struct stores the values for the sliders, so that they persist between frames
 // As EGUI is immediate mode, we have to maintain the state of the GUI ourselves
-#[derive(Resource, Clone)]
+#[derive(Resource, Clone, Serialize, Deserialize)]
 struct UiState {
     // scale: f64,
+    #[serde(with = "mat4_serde")]
     mat_transform: Mat4,
     ctrls_state: CtrlsState,
-    theta: f32,
+    affine: AffineDecomposition,
     ambient_brightness: f32,
 }
 
-#[derive(Resource, Clone)]
-struct WndState {
-    is_open_help_wnd: bool,
-    is_open_ctrl_wnd: bool,
-    is_open_status_wnd: bool,
+/// The dockable panels. Tabbed/split layout (and which of these are
+/// currently visible) lives in `DockLayout` instead of a per-window open
+/// flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum Tab {
+    Controls,
+    Status,
+    Help,
+    Timeline,
+    GamepadBindings,
+}
+
+impl Tab {
+    fn title(&self) -> &'static str {
+        match self {
+            Tab::Controls => "Controls",
+            Tab::Status => "Status",
+            Tab::Help => "Help",
+            Tab::Timeline => "Timeline",
+            Tab::GamepadBindings => "Gamepad Bindings",
+        }
+    }
+
+    fn all() -> [Tab; 5] {
+        [
+            Tab::Controls,
+            Tab::Status,
+            Tab::Help,
+            Tab::Timeline,
+            Tab::GamepadBindings,
+        ]
+    }
+}
+
+const DOCK_LAYOUT_PATH: &str = "dock_layout.ron";
+
+/// Wraps the `egui_dock` tree of `Tab`s so panels can be split/tabbed/
+/// snapped to the viewport edges instead of floating free. Persisted to
+/// `DOCK_LAYOUT_PATH` alongside the preset state, so a user's layout
+/// survives restarts the same way a saved matrix preset does.
+#[derive(Resource)]
+struct DockLayout {
+    state: egui_dock::DockState<Tab>,
 }
 
-impl Default for WndState {
+impl Default for DockLayout {
     fn default() -> Self {
         Self {
-            is_open_help_wnd: true,
-            is_open_ctrl_wnd: true,
-            is_open_status_wnd: true,
+            state: load_dock_layout().unwrap_or_else(default_dock_layout),
         }
     }
 }
 
+fn default_dock_layout() -> egui_dock::DockState<Tab> {
+    let mut state = egui_dock::DockState::new(vec![Tab::Controls]);
+    state.main_surface_mut().split_right(
+        egui_dock::NodeIndex::root(),
+        0.7,
+        vec![Tab::Status, Tab::Help, Tab::Timeline, Tab::GamepadBindings],
+    );
+    state
+}
+
+fn load_dock_layout() -> Option<egui_dock::DockState<Tab>> {
+    let ron = std::fs::read_to_string(DOCK_LAYOUT_PATH).ok()?;
+    ron::from_str(&ron).ok()
+}
+
+fn save_dock_layout(state: &egui_dock::DockState<Tab>) {
+    if let Ok(ron) = ron::ser::to_string_pretty(state, ron::ser::PrettyConfig::default()) {
+        let _ = std::fs::write(DOCK_LAYOUT_PATH, ron);
+    }
+}
+
 impl Default for UiState {
     fn default() -> Self {
         Self {
@@ -150,7 +232,144 @@ impl Default for UiState {
             // trying to do ..default() would cause a stack overflow here ;)
             mat_transform: default(),
             ctrls_state: default(),
-            theta: default(),
+            affine: default(),
+        }
+    }
+}
+
+/// Translation / rotation (Euler, radians) / scale / shear that the raw
+/// `Mat4` grid decomposes into and recomposes from, via [`decompose_affine`]
+/// and [`recompose_affine`]. Stored as plain `[f32; 3]`s rather than `Vec3`
+/// so it derives `serde` traits without a custom module, same as the rest
+/// of `UiState`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AffineDecomposition {
+    translation: [f32; 3],
+    rotation_euler: [f32; 3],
+    scale: [f32; 3],
+    shear_xy: f32,
+    shear_xz: f32,
+    shear_yz: f32,
+}
+
+impl Default for AffineDecomposition {
+    fn default() -> Self {
+        Self {
+            translation: [0.0; 3],
+            rotation_euler: [0.0; 3],
+            scale: [1.0; 3],
+            shear_xy: 0.0,
+            shear_xz: 0.0,
+            shear_yz: 0.0,
+        }
+    }
+}
+
+/// Below this, a scale axis is treated as collapsed and left un-normalized
+/// rather than dividing by (near) zero.
+const MIN_SCALE_FOR_NORMALIZE: f32 = 1e-6;
+
+/// Graphics Gems "unmatrix": decomposes the upper-left 3x3 plus translation
+/// of `m` into translation/rotation/scale/shear. Column 0 is normalized to
+/// give `scale.x`, then column 1 has its projection onto column 0 removed
+/// (giving `shear_xy`) before being normalized for `scale.y`, and likewise
+/// column 2 against both column 0 and column 1 for `shear_xz`/`shear_yz`/
+/// `scale.z`. If the resulting basis is left-handed the determinant is
+/// negative, so one scale axis (and its column) is flipped to compensate.
+fn decompose_affine(m: Mat4) -> AffineDecomposition {
+    let translation = m.w_axis.truncate();
+
+    let mut col0 = m.x_axis.truncate();
+    let mut col1 = m.y_axis.truncate();
+    let mut col2 = m.z_axis.truncate();
+
+    let mut scale = Vec3::ONE;
+
+    scale.x = col0.length();
+    if scale.x > MIN_SCALE_FOR_NORMALIZE {
+        col0 /= scale.x;
+    }
+
+    let mut shear_xy = col0.dot(col1);
+    col1 -= col0 * shear_xy;
+    scale.y = col1.length();
+    if scale.y > MIN_SCALE_FOR_NORMALIZE {
+        col1 /= scale.y;
+        shear_xy /= scale.y;
+    } else {
+        shear_xy = 0.0;
+    }
+
+    let mut shear_xz = col0.dot(col2);
+    col2 -= col0 * shear_xz;
+    let mut shear_yz = col1.dot(col2);
+    col2 -= col1 * shear_yz;
+    scale.z = col2.length();
+    if scale.z > MIN_SCALE_FOR_NORMALIZE {
+        col2 /= scale.z;
+        shear_xz /= scale.z;
+        shear_yz /= scale.z;
+    } else {
+        shear_xz = 0.0;
+        shear_yz = 0.0;
+    }
+
+    if col0.cross(col1).dot(col2) < 0.0 {
+        scale.x = -scale.x;
+        col0 = -col0;
+        // Both shears were measured against the un-flipped column 0.
+        shear_xy = -shear_xy;
+        shear_xz = -shear_xz;
+    }
+
+    let rotation = Quat::from_mat3(&Mat3::from_cols(col0, col1, col2));
+    let rotation_euler = rotation.to_euler(EulerRot::XYZ);
+
+    AffineDecomposition {
+        translation: translation.to_array(),
+        rotation_euler: [rotation_euler.0, rotation_euler.1, rotation_euler.2],
+        scale: scale.to_array(),
+        shear_xy,
+        shear_xz,
+        shear_yz,
+    }
+}
+
+/// Recomposes `M = T * R * Shear * S` from an [`AffineDecomposition`], the
+/// inverse of [`decompose_affine`].
+fn recompose_affine(a: &AffineDecomposition) -> Mat4 {
+    let translation = Mat4::from_translation(Vec3::from(a.translation));
+    let rotation_euler = a.rotation_euler;
+    let rotation = Mat4::from_quat(Quat::from_euler(
+        EulerRot::XYZ,
+        rotation_euler[0],
+        rotation_euler[1],
+        rotation_euler[2],
+    ));
+    let shear = Mat4::from_cols(
+        Vec4::new(1.0, 0.0, 0.0, 0.0),
+        Vec4::new(a.shear_xy, 1.0, 0.0, 0.0),
+        Vec4::new(a.shear_xz, a.shear_yz, 1.0, 0.0),
+        Vec4::new(0.0, 0.0, 0.0, 1.0),
+    );
+    let scale = Mat4::from_scale(Vec3::from(a.scale));
+    translation * rotation * shear * scale
+}
+
+/// Maps each `matrix_drag` `CtrlId` (0..16, in the grid's row-major UI
+/// order) to its index into `Mat4::to_cols_array`'s column-major layout.
+const CTRL_ID_TO_COL_MAJOR_INDEX: [usize; 16] =
+    [0, 1, 2, 12, 4, 5, 6, 13, 8, 9, 10, 14, 3, 7, 11, 15];
+
+/// Writes `m`'s 16 entries back into the `matrix_drag` cells, bypassing
+/// each cell's trig `CtrlMode` - the grid always shows the raw matrix.
+fn write_matrix_to_ctrls(ctrls: &mut CtrlsState, m: &Mat4) {
+    let cols = m.to_cols_array();
+    for (id, &col_index) in CTRL_ID_TO_COL_MAJOR_INDEX.iter().enumerate() {
+        let ctrl_id: CtrlId = id.into();
+        if let Some(state) = ctrls.0.get_mut(&ctrl_id) {
+            state.value = cols[col_index];
+            state.is_changed = false;
         }
     }
 }
@@ -161,9 +380,249 @@ struct Transformable {
     transform: Transform,
 }
 
+/// Spherical-coordinate orbit camera: rotates around `focus` at `radius`,
+/// recomputed into a `Transform` every frame by `orbit_camera_input`.
+#[derive(Component, Debug, Clone)]
+struct OrbitCamera {
+    focus: Vec3,
+    radius: f32,
+    azimuth: f32,
+    elevation: f32,
+    orbit_sensitivity: f32,
+    pan_sensitivity: f32,
+    zoom_sensitivity: f32,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self {
+            focus: Vec3::ZERO,
+            radius: 22.3,
+            azimuth: 0.0,
+            elevation: 0.45,
+            orbit_sensitivity: 0.006,
+            pan_sensitivity: 0.0015,
+            zoom_sensitivity: 0.8,
+        }
+    }
+}
+
+/// Elevation is clamped just shy of the poles to avoid the camera's "up"
+/// flipping (gimbal flip) when looking straight up/down.
+const ORBIT_ELEVATION_LIMIT: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
 #[derive(Resource, Default)]
 struct AssetsLoading(Vec<HandleUntyped>);
 
+/// Carries the path chosen in the background-thread `rfd` file dialog back
+/// to `handle_import` on the main `Update` schedule.
+#[derive(Resource)]
+struct ImportChannel {
+    sender: crossbeam_channel::Sender<PathBuf>,
+    receiver: crossbeam_channel::Receiver<PathBuf>,
+}
+
+impl Default for ImportChannel {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self { sender, receiver }
+    }
+}
+
+/// A single recorded pose at a point in time along the [`Timeline`].
+#[derive(Debug, Clone)]
+struct Keyframe {
+    time: f32,
+    matrix: Mat4,
+}
+
+/// Ordered list of keyframes plus transport state (play/pause/loop, the
+/// scrub position, playback speed). `timeline_playback` samples this while
+/// playing, or once after the playhead is scrubbed, and writes the blended
+/// pose into `UiState::mat_transform`.
+#[derive(Resource)]
+struct Timeline {
+    keyframes: Vec<Keyframe>,
+    playhead: f32,
+    playing: bool,
+    looping: bool,
+    speed: f32,
+    /// Playhead value [`timeline_playback`] last sampled and applied, so a
+    /// manual scrub (playhead changed while paused) still previews its
+    /// pose exactly once instead of needing playback to resample it, while
+    /// the matrix stays put for manual editing the rest of the time.
+    last_applied_playhead: Option<f32>,
+}
+
+impl Timeline {
+    /// Finds the keyframes bracketing `t` and blends between them; clamps
+    /// to the first/last keyframe outside their range.
+    fn sample(&self, t: f32) -> Option<Mat4> {
+        match self.keyframes.as_slice() {
+            [] => None,
+            [only] => Some(only.matrix),
+            keyframes => {
+                if t <= keyframes[0].time {
+                    return Some(keyframes[0].matrix);
+                }
+                if t >= keyframes[keyframes.len() - 1].time {
+                    return Some(keyframes[keyframes.len() - 1].matrix);
+                }
+                keyframes.windows(2).find_map(|pair| {
+                    let (k0, k1) = (&pair[0], &pair[1]);
+                    if t < k0.time || t > k1.time {
+                        return None;
+                    }
+                    let span = (k1.time - k0.time).max(f32::EPSILON);
+                    let alpha = (t - k0.time) / span;
+                    Some(blend_mat4(k0.matrix, k1.matrix, alpha))
+                })
+            }
+        }
+    }
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self {
+            keyframes: Vec::new(),
+            playhead: 0.0,
+            playing: false,
+            looping: false,
+            speed: 1.0,
+            last_applied_playhead: None,
+        }
+    }
+}
+
+/// Blends two poses by decomposing each into scale/rotation/translation,
+/// LERP-ing scale and translation and SLERP-ing the rotation quaternion -
+/// the standard way to interpolate rigid(-ish) transforms without the
+/// skewing a naive component-wise `Mat4` lerp would produce.
+fn blend_mat4(a: Mat4, b: Mat4, alpha: f32) -> Mat4 {
+    let (scale_a, rotation_a, translation_a) = a.to_scale_rotation_translation();
+    let (scale_b, rotation_b, translation_b) = b.to_scale_rotation_translation();
+    Mat4::from_scale_rotation_translation(
+        scale_a.lerp(scale_b, alpha),
+        rotation_a.slerp(rotation_b, alpha),
+        translation_a.lerp(translation_b, alpha),
+    )
+}
+
+/// Advances the playhead (looping or stopping at the end) and writes the
+/// sampled pose into both `UiState::mat_transform` and the `matrix_drag`
+/// cells so the model animates and the Controls window stays in sync. Only
+/// does so while playing, or the frame a scrub moves the playhead while
+/// paused - otherwise it would stomp on manual matrix/TRS edits every frame
+/// as soon as a single keyframe existed.
+fn timeline_playback(time: Res<Time>, mut timeline: ResMut<Timeline>, mut ui_state: ResMut<UiState>) {
+    if timeline.playing {
+        if let Some(last) = timeline.keyframes.last() {
+            let end = last.time;
+            timeline.playhead += time.delta_seconds() * timeline.speed;
+            if timeline.playhead >= end {
+                if timeline.looping && end > f32::EPSILON {
+                    timeline.playhead %= end;
+                } else {
+                    timeline.playhead = end;
+                    timeline.playing = false;
+                }
+            }
+        } else {
+            timeline.playing = false;
+        }
+    }
+
+    let scrubbed = Some(timeline.playhead) != timeline.last_applied_playhead;
+    if timeline.playing || scrubbed {
+        if let Some(sampled) = timeline.sample(timeline.playhead) {
+            ui_state.mat_transform = sampled;
+            ui_state.affine = decompose_affine(sampled);
+            write_matrix_to_ctrls(&mut ui_state.ctrls_state, &sampled);
+        }
+    }
+    timeline.last_applied_playhead = Some(timeline.playhead);
+}
+
+/// Transport bar: play/pause/loop, a scrub slider, playback speed, and
+/// add/delete-keyframe buttons that snapshot/remove the pose at the
+/// playhead. Drawn into the "Timeline" dock tab by [`DockTabViewer::ui`].
+fn timeline_ui(ui: &mut Ui, timeline: &mut Timeline, ui_state: &UiState) {
+    ui.horizontal(|ui| {
+        if ui.button(if timeline.playing { "Pause" } else { "Play" }).clicked() {
+            timeline.playing = !timeline.playing;
+        }
+        ui.checkbox(&mut timeline.looping, "Loop");
+        let label = ui.label("Speed");
+        ui.add(
+            DragValue::new(&mut timeline.speed)
+                .speed(0.01)
+                .clamp_range(0.1..=4.0),
+        )
+        .labelled_by(label.id);
+    });
+    let end = timeline.keyframes.last().map(|k| k.time).unwrap_or(0.0).max(0.01);
+    ui.add(egui::Slider::new(&mut timeline.playhead, 0.0..=end).text("Playhead"));
+    ui.horizontal(|ui| {
+        if ui.button("Add keyframe at playhead").clicked() {
+            let t = timeline.playhead;
+            timeline.keyframes.retain(|k| (k.time - t).abs() > f32::EPSILON);
+            timeline.keyframes.push(Keyframe {
+                time: t,
+                matrix: ui_state.mat_transform,
+            });
+            timeline
+                .keyframes
+                .sort_by(|a, b| a.time.total_cmp(&b.time));
+        }
+        if ui.button("Delete keyframe").clicked() {
+            let t = timeline.playhead;
+            timeline.keyframes.retain(|k| (k.time - t).abs() > 1e-3);
+        }
+    });
+    ui.label(format!("{} keyframe(s)", timeline.keyframes.len()));
+}
+
+/// Directory the preset Save/Load dialogs open in and `list_presets` reads,
+/// so presets saved there show up in the named-preset dropdown.
+const PRESETS_DIR: &str = "presets";
+
+/// Tracks the file a preset was last saved to/loaded from, so a plain
+/// "Save" can write back without prompting again.
+#[derive(Resource, Default)]
+struct PresetState {
+    current_path: Option<PathBuf>,
+    selected_preset: Option<String>,
+}
+
+fn save_ui_state(ui_state: &UiState, path: &std::path::Path) -> Result<(), String> {
+    let ron = ron::ser::to_string_pretty(ui_state, ron::ser::PrettyConfig::default())
+        .map_err(|e| e.to_string())?;
+    std::fs::write(path, ron).map_err(|e| e.to_string())
+}
+
+fn load_ui_state(path: &std::path::Path) -> Result<UiState, String> {
+    let ron = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    ron::from_str(&ron).map_err(|e| e.to_string())
+}
+
+/// Lists the `.ron` presets sitting in `PRESETS_DIR`, by file stem.
+fn list_presets() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(PRESETS_DIR) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().and_then(|e| e.to_str()) == Some("ron"))
+        .filter_map(|entry| {
+            entry
+                .path()
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+        .collect()
+}
+
 #[derive(Resource, Default)]
 struct PgmStatus {
     last_fps: f64,
@@ -171,6 +630,181 @@ struct PgmStatus {
     update_timer: Timer,
 }
 
+/// Deadzone below which stick/trigger input is ignored, to avoid drift.
+const GAMEPAD_DEADZONE: f32 = 0.15;
+
+/// How fast the left stick nudges the currently selected `CtrlId`'s value.
+const GAMEPAD_NUDGE_SPEED: f32 = 0.6;
+
+/// How fast the right stick orbits the camera, in radians/sec at full deflection.
+const GAMEPAD_ORBIT_SPEED: f32 = 2.0;
+
+/// Which axis/button drives which action. The two stick axes can be rebound
+/// in the "Gamepad Bindings" tab without recompiling; the button bindings
+/// are not exposed there and keep their defaults.
+#[derive(Debug, Clone)]
+struct GamepadBindings {
+    nudge_stick: GamepadAxisType,
+    orbit_stick: GamepadAxisType,
+    reset_matrix_button: GamepadButtonType,
+    reset_mode_button: GamepadButtonType,
+    cycle_mode_button: GamepadButtonType,
+}
+
+impl Default for GamepadBindings {
+    fn default() -> Self {
+        Self {
+            nudge_stick: GamepadAxisType::LeftStickX,
+            orbit_stick: GamepadAxisType::RightStickX,
+            reset_matrix_button: GamepadButtonType::South,
+            reset_mode_button: GamepadButtonType::East,
+            cycle_mode_button: GamepadButtonType::North,
+        }
+    }
+}
+
+/// Tracks the connected gamepad (if any), its axis/button bindings, and
+/// which `CtrlId` the left stick is currently nudging.
+#[derive(Resource, Debug, Default)]
+struct GamepadState {
+    gamepad: Option<Gamepad>,
+    bindings: GamepadBindings,
+    selected_ctrl: usize,
+}
+
+fn apply_deadzone(v: f32) -> f32 {
+    if v.abs() < GAMEPAD_DEADZONE {
+        0.0
+    } else {
+        v
+    }
+}
+
+/// Tracks gamepad connect/disconnect events so `gamepad_input` always has a
+/// live `Gamepad` (or `None`) to read from.
+fn gamepad_connections(
+    mut gamepad_state: ResMut<GamepadState>,
+    mut events: EventReader<GamepadConnectionEvent>,
+) {
+    for event in events.iter() {
+        if event.connected() {
+            gamepad_state.gamepad.get_or_insert(event.gamepad);
+        } else if gamepad_state.gamepad == Some(event.gamepad) {
+            gamepad_state.gamepad = None;
+        }
+    }
+}
+
+/// Reads the left stick (nudge the selected `matrix_drag` cell), the right
+/// stick (orbit the camera), and the face buttons (reset/cycle mode), so the
+/// demo is fully drivable from a controller.
+fn gamepad_input(
+    gamepad_state: Res<GamepadState>,
+    axes: Res<Axis<GamepadAxis>>,
+    buttons: Res<Input<GamepadButton>>,
+    time: Res<Time>,
+    mut ui_state: ResMut<UiState>,
+    mut camera: Query<&mut OrbitCamera>,
+) {
+    let Some(gamepad) = gamepad_state.gamepad else {
+        return;
+    };
+
+    let nudge = axes
+        .get(GamepadAxis::new(gamepad, gamepad_state.bindings.nudge_stick))
+        .map(apply_deadzone)
+        .unwrap_or(0.0);
+    if nudge != 0.0 {
+        let id: CtrlId = gamepad_state.selected_ctrl.into();
+        if let Some(ctrl) = ui_state.ctrls_state.0.get_mut(&id) {
+            ctrl.value += nudge * GAMEPAD_NUDGE_SPEED * time.delta_seconds();
+            ctrl.is_changed = true;
+        }
+    }
+
+    let orbit = axes
+        .get(GamepadAxis::new(gamepad, gamepad_state.bindings.orbit_stick))
+        .map(apply_deadzone)
+        .unwrap_or(0.0);
+    if orbit != 0.0 {
+        if let Ok(mut orbit_camera) = camera.get_single_mut() {
+            orbit_camera.azimuth -= orbit * GAMEPAD_ORBIT_SPEED * time.delta_seconds();
+        }
+    }
+
+    if buttons.just_pressed(GamepadButton::new(
+        gamepad,
+        gamepad_state.bindings.reset_matrix_button,
+    )) {
+        ui_state.ctrls_state.reset_values();
+    }
+    if buttons.just_pressed(GamepadButton::new(
+        gamepad,
+        gamepad_state.bindings.reset_mode_button,
+    )) {
+        ui_state.ctrls_state.reset_modes();
+    }
+    if buttons.just_pressed(GamepadButton::new(
+        gamepad,
+        gamepad_state.bindings.cycle_mode_button,
+    )) {
+        let id: CtrlId = gamepad_state.selected_ctrl.into();
+        if let Some(ctrl) = ui_state.ctrls_state.0.get_mut(&id) {
+            let first = CtrlMode::iter().next().expect("Could not get first value!");
+            ctrl.mode = CtrlMode::iter()
+                .skip_while(|m| *m != ctrl.mode)
+                .nth(1)
+                .unwrap_or(first);
+            ctrl.is_changed = true;
+        }
+    }
+}
+
+/// Small remapping table so users can pick which stick axis nudges the
+/// selected cell and which orbits the camera.
+/// Drawn into the "Gamepad Bindings" dock tab by [`DockTabViewer::ui`].
+fn gamepad_bindings_ui(ui: &mut Ui, gamepad_state: &mut GamepadState) {
+    let connected = gamepad_state.gamepad.is_some();
+    let mut bindings = gamepad_state.bindings.clone();
+    let mut selected_ctrl = gamepad_state.selected_ctrl;
+    ui.label(if connected {
+        "Gamepad connected."
+    } else {
+        "No gamepad connected."
+    });
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.label("Selected cell (CtrlId):");
+        ui.add(DragValue::new(&mut selected_ctrl).clamp_range(0..=15));
+    });
+    egui::ComboBox::from_label("Nudge stick")
+        .selected_text(format!("{:?}", bindings.nudge_stick))
+        .show_ui(ui, |ui| {
+            for axis in [
+                GamepadAxisType::LeftStickX,
+                GamepadAxisType::LeftStickY,
+                GamepadAxisType::RightStickX,
+                GamepadAxisType::RightStickY,
+            ] {
+                ui.selectable_value(&mut bindings.nudge_stick, axis, format!("{axis:?}"));
+            }
+        });
+    egui::ComboBox::from_label("Orbit stick")
+        .selected_text(format!("{:?}", bindings.orbit_stick))
+        .show_ui(ui, |ui| {
+            for axis in [
+                GamepadAxisType::LeftStickX,
+                GamepadAxisType::LeftStickY,
+                GamepadAxisType::RightStickX,
+                GamepadAxisType::RightStickY,
+            ] {
+                ui.selectable_value(&mut bindings.orbit_stick, axis, format!("{axis:?}"));
+            }
+        });
+    gamepad_state.bindings = bindings;
+    gamepad_state.selected_ctrl = selected_ctrl;
+}
+
 // Main entrypoint
 fn main() {
     // App entrypoint
@@ -186,32 +820,26 @@ fn main() {
         // Resources (live data that can be accessed from any system)
         .init_resource::<AssetsLoading>()
         .init_resource::<UiState>()
-        .init_resource::<WndState>()
+        .init_resource::<DockLayout>()
         .init_resource::<PgmStatus>()
+        .init_resource::<GamepadState>()
+        .init_resource::<ImportChannel>()
+        .init_resource::<PresetState>()
+        .init_resource::<Timeline>()
         .insert_resource(DirectionalLightShadowMap { size: 4096 })
         .add_plugins(bevy_egui::EguiPlugin)
         // Systems (functions that are called at regular intervals)
         .add_systems(Startup, setup)
-        .add_systems(Update, window_view)
-        .add_systems(
-            Update,
-            window_help.run_if(IntoSystem::into_system(|wnd_state: Res<WndState>| {
-                wnd_state.is_open_help_wnd == true
-            })),
-        )
-        .add_systems(
-            Update,
-            window_ctrl.run_if(IntoSystem::into_system(|wnd_state: Res<WndState>| {
-                wnd_state.is_open_ctrl_wnd == true
-            })),
-        )
-        .add_systems(
-            Update,
-            ui_status.run_if(IntoSystem::into_system(|wnd_state: Res<WndState>| {
-                wnd_state.is_open_status_wnd == true
-            })),
-        )
+        .add_systems(Update, menu_bar)
+        .add_systems(Update, handle_import)
+        .add_systems(Update, timeline_playback.before(ui_dock_area))
+        .add_systems(Update, apply_transform)
+        .add_systems(Update, ui_dock_area)
         .add_systems(Update, keyboard_input)
+        .add_systems(Update, gamepad_connections)
+        .add_systems(Update, gamepad_input.after(gamepad_connections))
+        .add_systems(Update, orbit_camera_input.after(gamepad_input))
+        .add_systems(Update, persist_dock_layout_on_exit)
         .run(); // Event loop etc occurs here
 }
 
@@ -259,10 +887,15 @@ fn setup(
         update_timer: Timer::new(Duration::from_millis(100), TimerMode::Repeating),
         ..default()
     });
-    commands.spawn(Camera3dBundle {
-        transform: Transform::from_xyz(0.0, 10.0, 20.0).looking_at(Vec3::ZERO, Vec3::Y),
-        ..default()
-    });
+    let orbit_camera = OrbitCamera::default();
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_translation(orbit_camera_position(&orbit_camera))
+                .looking_at(orbit_camera.focus, Vec3::Y),
+            ..default()
+        },
+        orbit_camera,
+    ));
 
     commands.spawn(PointLightBundle {
         transform: Transform::from_translation(Vec3::ONE * 3.0),
@@ -354,6 +987,7 @@ impl EguiExtras for Ui {
 
 #[inline]
 fn mat4_ui<'a>(ui: &mut Ui, ui_state: &mut UiState, value: &mut Mat4) {
+    let value_before_grid = *value;
     let s = &mut ui_state.ctrls_state;
     ui.strong("Direct Matrix Control");
     ui.group(|ui| {
@@ -407,44 +1041,79 @@ fn mat4_ui<'a>(ui: &mut Ui, ui_state: &mut UiState, value: &mut Mat4) {
     });
     ui.separator();
     ui.strong("High-level Controls");
+    // The grid above may have just changed `value` directly (a raw cell
+    // drag); if so, re-decompose it so the TRS/shear sliders below reflect
+    // the grid instead of fighting it.
+    if *value != value_before_grid {
+        ui_state.affine = decompose_affine(*value);
+    }
+    let affine = &mut ui_state.affine;
+    let mut affine_changed = false;
+    ui.label("Translation");
+    ui.horizontal(|ui| {
+        affine_changed |= ui.add(DragValue::new(&mut affine.translation[0]).speed(0.02).prefix("x: ")).changed();
+        affine_changed |= ui.add(DragValue::new(&mut affine.translation[1]).speed(0.02).prefix("y: ")).changed();
+        affine_changed |= ui.add(DragValue::new(&mut affine.translation[2]).speed(0.02).prefix("z: ")).changed();
+    });
+    ui.label("Rotation (radians)");
     ui.horizontal(|ui| {
-        let label = ui.label("Theta");
-        let handle = ui
+        affine_changed |= ui
             .add(
-                DragValue::new(&mut ui_state.theta)
+                DragValue::new(&mut affine.rotation_euler[0])
                     .speed(0.01)
-                    .clamp_range(-FOUR_PI..=FOUR_PI),
+                    .clamp_range(-FOUR_PI..=FOUR_PI)
+                    .prefix("x: "),
             )
-            .labelled_by(label.id);
-        if handle.changed() {
-            for (_, state) in ui_state.ctrls_state.0.iter_mut() {
-                match state.mode {
-                    CtrlMode::Normal => {}
-                    _ => {
-                        state.value = ui_state.theta;
-                        state.is_changed = true;
-                    }
-                }
-            }
-        }
+            .changed();
+        affine_changed |= ui
+            .add(
+                DragValue::new(&mut affine.rotation_euler[1])
+                    .speed(0.01)
+                    .clamp_range(-FOUR_PI..=FOUR_PI)
+                    .prefix("y: "),
+            )
+            .changed();
+        affine_changed |= ui
+            .add(
+                DragValue::new(&mut affine.rotation_euler[2])
+                    .speed(0.01)
+                    .clamp_range(-FOUR_PI..=FOUR_PI)
+                    .prefix("z: "),
+            )
+            .changed();
+    });
+    ui.label("Scale");
+    ui.horizontal(|ui| {
+        affine_changed |= ui.add(DragValue::new(&mut affine.scale[0]).speed(0.02).prefix("x: ")).changed();
+        affine_changed |= ui.add(DragValue::new(&mut affine.scale[1]).speed(0.02).prefix("y: ")).changed();
+        affine_changed |= ui.add(DragValue::new(&mut affine.scale[2]).speed(0.02).prefix("z: ")).changed();
+    });
+    ui.label("Shear");
+    ui.horizontal(|ui| {
+        affine_changed |= ui.add(DragValue::new(&mut affine.shear_xy).speed(0.01).prefix("xy: ")).changed();
+        affine_changed |= ui.add(DragValue::new(&mut affine.shear_xz).speed(0.01).prefix("xz: ")).changed();
+        affine_changed |= ui.add(DragValue::new(&mut affine.shear_yz).speed(0.01).prefix("yz: ")).changed();
     });
+    if affine_changed {
+        *value = recompose_affine(affine);
+        write_matrix_to_ctrls(&mut ui_state.ctrls_state, value);
+    }
     ui.separator();
     ui.strong("Matrix Info");
     ui.label(format!("Determinant: {}", value.determinant()))
         .on_hover_text("The change in volume applied by this transform (ignoring w_axis).");
 }
 
-fn ui_status(
-    mut ctx: EguiContexts,
-    time: Res<Time>,
-    frame_count: Res<FrameCount>,
-    egui_settings: Res<EguiSettings>,
-    mut status: ResMut<PgmStatus>,
-    // mut ui_state: ResMut<UiState>,
-    loading: Option<Res<AssetsLoading>>,
-    server: Res<AssetServer>,
-    mut wnd_state: ResMut<WndState>,
-    mut commands: Commands, // mut ui_state: ResMut<UiState>,
+/// Ticks the FPS timer and draws the "Status" dock tab's contents.
+fn status_ui(
+    ui: &mut Ui,
+    time: &Time,
+    frame_count: &FrameCount,
+    egui_settings: &EguiSettings,
+    status: &mut PgmStatus,
+    loading: &Option<Res<AssetsLoading>>,
+    server: &AssetServer,
+    commands: &mut Commands,
 ) {
     let delta_frame_count = frame_count.0 - status.last_frame_count;
     status.last_frame_count = frame_count.0;
@@ -454,29 +1123,81 @@ fn ui_status(
         let fps = delta_frame_count as f64 / t;
         status.last_fps = fps;
     }
-    egui::Window::new("Status")
-        .open(&mut wnd_state.is_open_status_wnd)
-        .resize(|r| r.default_size(bevy_egui::egui::Vec2::ZERO))
-        .show(ctx.ctx_mut(), |ui| {
-            ui.label(format!("FPS: {:.2}", status.last_fps));
-            ui.separator();
-
-            ui.label(format!("Scale factor: {:.2}", egui_settings.scale_factor));
-            if let Some(loading) = loading {
-                ui.separator();
-                match server.get_group_load_state(loading.0.iter().map(|h| h.id())) {
-                    bevy::asset::LoadState::Loaded => {
-                        commands.remove_resource::<AssetsLoading>();
-                    }
-                    _ => {
-                        ui.horizontal(|ui| {
-                            ui.add(egui::Spinner::new());
-                            ui.label("Still loading assets...");
-                        });
-                    }
-                }
+    ui.label(format!("FPS: {:.2}", status.last_fps));
+    ui.separator();
+
+    ui.label(format!("Scale factor: {:.2}", egui_settings.scale_factor));
+    if let Some(loading) = loading {
+        ui.separator();
+        match server.get_group_load_state(loading.0.iter().map(|h| h.id())) {
+            bevy::asset::LoadState::Loaded => {
+                commands.remove_resource::<AssetsLoading>();
             }
-        });
+            _ => {
+                ui.horizontal(|ui| {
+                    ui.add(egui::Spinner::new());
+                    ui.label("Still loading assets...");
+                });
+            }
+        }
+    }
+}
+
+/// Computes the camera's world position from its spherical coordinates.
+fn orbit_camera_position(orbit_camera: &OrbitCamera) -> Vec3 {
+    let dir = Vec3::new(
+        orbit_camera.elevation.cos() * orbit_camera.azimuth.sin(),
+        orbit_camera.elevation.sin(),
+        orbit_camera.elevation.cos() * orbit_camera.azimuth.cos(),
+    );
+    orbit_camera.focus + dir * orbit_camera.radius
+}
+
+/// Left-drag orbits, middle/shift-drag pans, and the wheel zooms. Skips all
+/// input while egui wants the pointer, so dragging sliders doesn't spin the
+/// camera.
+fn orbit_camera_input(
+    mut mouse_motion: EventReader<bevy::input::mouse::MouseMotion>,
+    mut mouse_wheel: EventReader<bevy::input::mouse::MouseWheel>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    keys: Res<Input<KeyCode>>,
+    mut ctx: EguiContexts,
+    mut camera: Query<(&mut Transform, &mut OrbitCamera)>,
+) {
+    // Drop this frame's pointer events but still recompute the transform, so
+    // gamepad and Controls-tab changes keep applying.
+    if ctx.ctx_mut().wants_pointer_input() {
+        mouse_motion.clear();
+        mouse_wheel.clear();
+    }
+
+    let Ok((mut transform, mut orbit_camera)) = camera.get_single_mut() else {
+        return;
+    };
+
+    let delta: Vec2 = mouse_motion.iter().map(|e| e.delta).sum();
+    let shift_held = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    if mouse_buttons.pressed(MouseButton::Left) && !shift_held {
+        orbit_camera.azimuth -= delta.x * orbit_camera.orbit_sensitivity;
+        orbit_camera.elevation = (orbit_camera.elevation + delta.y * orbit_camera.orbit_sensitivity)
+            .clamp(-ORBIT_ELEVATION_LIMIT, ORBIT_ELEVATION_LIMIT);
+    } else if mouse_buttons.pressed(MouseButton::Middle)
+        || (mouse_buttons.pressed(MouseButton::Left) && shift_held)
+    {
+        let pan = (transform.right() * -delta.x + transform.up() * delta.y)
+            * orbit_camera.pan_sensitivity
+            * orbit_camera.radius;
+        orbit_camera.focus += pan;
+    }
+
+    for wheel in mouse_wheel.iter() {
+        orbit_camera.radius =
+            (orbit_camera.radius - wheel.y * orbit_camera.zoom_sensitivity).max(0.5);
+    }
+
+    transform.translation = orbit_camera_position(&orbit_camera);
+    *transform = transform.looking_at(orbit_camera.focus, Vec3::Y);
 }
 
 fn keyboard_input(keys: Res<Input<KeyCode>>, mut egui_settings: ResMut<EguiSettings>) {
@@ -493,49 +1214,361 @@ fn keyboard_input(keys: Res<Input<KeyCode>>, mut egui_settings: ResMut<EguiSetti
     }
 }
 
-fn window_help(mut ctx: EguiContexts, mut wnd_state: ResMut<WndState>) {
-    egui::Window::new("Help")
-        .open(&mut wnd_state.is_open_help_wnd)
-        .show(ctx.ctx_mut(), |ui| {
-            ui.label("Ctrl+Plus to increase UI size.");
-            ui.label("Ctrl+Minus to decrease UI size.");
-        });
+fn help_ui(ui: &mut Ui) {
+    ui.label("Ctrl+Plus to increase UI size.");
+    ui.label("Ctrl+Minus to decrease UI size.");
 }
 
-fn window_view(mut wnd_state: ResMut<WndState>, mut ctx: EguiContexts) {
-    egui::Window::new("View").show(ctx.ctx_mut(), |ui| {
-        ui.checkbox(&mut wnd_state.is_open_help_wnd, "Show Help Window");
-        ui.checkbox(&mut wnd_state.is_open_ctrl_wnd, "Show Control Window");
-        ui.checkbox(&mut wnd_state.is_open_status_wnd, "Show Status Window");
+/// "File -> Import Model..." opens an `rfd::FileDialog` on a background
+/// thread (so we don't block the render loop) and sends the chosen path
+/// back over `ImportChannel` for `handle_import` to pick up. "View" re-opens
+/// any dock tab the user has closed, since closing a tab (unlike the old
+/// floating windows) drops it from the layout entirely.
+fn menu_bar(
+    mut ctx: EguiContexts,
+    import_channel: Res<ImportChannel>,
+    mut dock_layout: ResMut<DockLayout>,
+) {
+    egui::TopBottomPanel::top("menu_bar").show(ctx.ctx_mut(), |ui| {
+        egui::menu::bar(ui, |ui| {
+            ui.menu_button("File", |ui| {
+                if ui.button("Import Model...").clicked() {
+                    let sender = import_channel.sender.clone();
+                    std::thread::spawn(move || {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("3D Models", &["gltf", "glb", "stl"])
+                            .pick_file()
+                        {
+                            let _ = sender.send(path);
+                        }
+                    });
+                    ui.close_menu();
+                }
+            });
+            ui.menu_button("View", |ui| {
+                for tab in Tab::all() {
+                    let is_open = dock_layout.state.iter_all_tabs().any(|(_, t)| *t == tab);
+                    if ui
+                        .add_enabled(!is_open, egui::Button::new(tab.title()))
+                        .clicked()
+                    {
+                        dock_layout.state.push_to_focused_leaf(tab);
+                        ui.close_menu();
+                    }
+                }
+            });
+        });
     });
 }
 
-fn window_ctrl(
-    mut transformable: Query<(&mut Transform, &Transformable)>,
-    mut ui_state: ResMut<UiState>,
-    mut wnd_state: ResMut<WndState>,
-    mut ctx: EguiContexts,
-    mut ambient_light: ResMut<AmbientLight>,
+/// Parses a binary STL file into a Bevy `Mesh`: 80-byte header, a `u32`
+/// triangle count, then per-triangle 12 floats - a 3-float normal (discarded
+/// - we recompute flat normals ourselves since many exporters leave it
+/// zeroed) followed by its 3 vertices - and a 2-byte attribute count.
+fn parse_stl_mesh(bytes: &[u8]) -> Mesh {
+    const HEADER_LEN: usize = 84;
+    const TRIANGLE_LEN: usize = 12 * 4 + 2;
+
+    let mut positions = Vec::new();
+    if bytes.len() >= HEADER_LEN {
+        let triangle_count =
+            u32::from_le_bytes(bytes[80..84].try_into().expect("checked length above")) as usize;
+        let mut offset = HEADER_LEN;
+        for _ in 0..triangle_count {
+            if offset + TRIANGLE_LEN > bytes.len() {
+                break;
+            }
+            offset += 12; // skip the stored normal
+            for _ in 0..3 {
+                let x = f32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+                let y = f32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap());
+                let z = f32::from_le_bytes(bytes[offset + 8..offset + 12].try_into().unwrap());
+                positions.push([x, y, z]);
+                offset += 12;
+            }
+            offset += 2; // attribute byte count
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.compute_flat_normals();
+    mesh
+}
+
+/// Despawns the current `Transformable` model and spawns whatever path
+/// `menu_bar` sent through `ImportChannel`, dispatching on extension: glTF/
+/// GLB go through the normal asset server, STL is parsed by hand.
+fn handle_import(
+    import_channel: Res<ImportChannel>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut loading: Option<ResMut<AssetsLoading>>,
+    transformable: Query<Entity, With<Transformable>>,
 ) {
-    egui::Window::new("Controls")
-        .open(&mut wnd_state.is_open_ctrl_wnd)
-        .resize(|r| r.default_size(bevy_egui::egui::Vec2::ZERO))
-        .show(ctx.ctx_mut(), |ui| {
-            // Moooooom. The borrow checker is bullying me Y~Y
-            let mut cloned_ui_mat = ui_state.mat_transform;
-            mat4_ui(ui, &mut ui_state, &mut cloned_ui_mat);
-            ui_state.mat_transform = cloned_ui_mat;
-            ambient_light.brightness = ui_state.ambient_brightness;
-            ui.separator();
-            ui.strong("Display Settings");
-            ui.horizontal(|ui| {
-                let label = ui.label("Ambient Brightness:");
-                ui.add(DragValue::new(&mut ui_state.ambient_brightness).speed(0.001))
-                    .labelled_by(label.id);
+    let Ok(path) = import_channel.receiver.try_recv() else {
+        return;
+    };
+
+    for entity in &transformable {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let transformable = Transformable {
+        transform: Transform::from_scale(Vec3::splat(10.))
+            .with_translation(Vec3::new(0., -10., -3.)),
+    };
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gltf") | Some("glb") => {
+            let handle = asset_server.load(format!("{}#Scene0", path.display()));
+            // `status_ui` removes `AssetsLoading` once the initial load is done.
+            match loading.as_mut() {
+                Some(loading) => loading.0.push(handle.clone_untyped()),
+                None => commands.insert_resource(AssetsLoading(vec![handle.clone_untyped()])),
+            }
+            commands.spawn((
+                SceneBundle {
+                    scene: handle,
+                    transform: Transform::default(),
+                    ..default()
+                },
+                transformable,
+            ));
+        }
+        Some("stl") => {
+            let Ok(bytes) = std::fs::read(&path) else {
+                return;
+            };
+            commands.spawn((
+                PbrBundle {
+                    mesh: meshes.add(parse_stl_mesh(&bytes)),
+                    material: materials.add(Color::rgb(0.75, 0.75, 0.75).into()),
+                    transform: Transform::default(),
+                    ..default()
+                },
+                transformable,
+            ));
+        }
+        _ => {}
+    }
+}
+
+/// Draws the "Controls" dock tab: the matrix grid/TRS controls, display and
+/// camera settings, and preset save/load. Unlike the old floating window,
+/// this no longer gates [`apply_transform`] - closing the tab just hides the
+/// editor, it doesn't freeze the model's pose.
+fn controls_ui(
+    ui: &mut Ui,
+    ui_state: &mut UiState,
+    orbit_camera: &mut Query<&mut OrbitCamera>,
+    preset_state: &mut PresetState,
+    ambient_light: &mut AmbientLight,
+) {
+    // Moooooom. The borrow checker is bullying me Y~Y
+    let mut cloned_ui_mat = ui_state.mat_transform;
+    mat4_ui(ui, ui_state, &mut cloned_ui_mat);
+    ui_state.mat_transform = cloned_ui_mat;
+    ambient_light.brightness = ui_state.ambient_brightness;
+    ui.separator();
+    ui.strong("Display Settings");
+    ui.horizontal(|ui| {
+        let label = ui.label("Ambient Brightness:");
+        ui.add(DragValue::new(&mut ui_state.ambient_brightness).speed(0.001))
+            .labelled_by(label.id);
+    });
+    if let Ok(mut orbit_camera) = orbit_camera.get_single_mut() {
+        ui.separator();
+        ui.strong("Camera");
+        ui.horizontal(|ui| {
+            ui.label("Focus:");
+            ui.add(DragValue::new(&mut orbit_camera.focus.x).speed(0.05).prefix("x: "));
+            ui.add(DragValue::new(&mut orbit_camera.focus.y).speed(0.05).prefix("y: "));
+            ui.add(DragValue::new(&mut orbit_camera.focus.z).speed(0.05).prefix("z: "));
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label("Radius:");
+            ui.add(
+                DragValue::new(&mut orbit_camera.radius)
+                    .speed(0.1)
+                    .clamp_range(0.5..=200.0),
+            )
+            .labelled_by(label.id);
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label("Orbit sensitivity:");
+            ui.add(DragValue::new(&mut orbit_camera.orbit_sensitivity).speed(0.0005))
+                .labelled_by(label.id);
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label("Pan sensitivity:");
+            ui.add(DragValue::new(&mut orbit_camera.pan_sensitivity).speed(0.0001))
+                .labelled_by(label.id);
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label("Zoom sensitivity:");
+            ui.add(DragValue::new(&mut orbit_camera.zoom_sensitivity).speed(0.01))
+                .labelled_by(label.id);
+        });
+    }
+    ui.separator();
+    ui.strong("Presets");
+    ui.horizontal(|ui| {
+        if ui.button("Save").clicked() {
+            let path = preset_state.current_path.clone().or_else(|| {
+                rfd::FileDialog::new()
+                    .add_filter("Preset", &["ron"])
+                    .set_directory(PRESETS_DIR)
+                    .save_file()
             });
+            if let Some(path) = path {
+                if let Err(err) = save_ui_state(ui_state, &path) {
+                    eprintln!("Failed to save preset: {err}");
+                } else {
+                    preset_state.current_path = Some(path);
+                }
+            }
+        }
+        if ui.button("Save As...").clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Preset", &["ron"])
+                .set_directory(PRESETS_DIR)
+                .save_file()
+            {
+                if let Err(err) = save_ui_state(ui_state, &path) {
+                    eprintln!("Failed to save preset: {err}");
+                } else {
+                    preset_state.current_path = Some(path);
+                }
+            }
+        }
+        if ui.button("Load...").clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Preset", &["ron"])
+                .set_directory(PRESETS_DIR)
+                .pick_file()
+            {
+                match load_ui_state(&path) {
+                    Ok(loaded) => {
+                        *ui_state = loaded;
+                        preset_state.current_path = Some(path);
+                        preset_state.selected_preset = None;
+                    }
+                    Err(err) => eprintln!("Failed to load preset: {err}"),
+                }
+            }
+        }
+    });
+    egui::ComboBox::from_label("Named preset")
+        .selected_text(preset_state.selected_preset.as_deref().unwrap_or("-"))
+        .show_ui(ui, |ui| {
+            for name in list_presets() {
+                let is_selected = preset_state.selected_preset.as_deref() == Some(&name);
+                if ui.selectable_label(is_selected, &name).clicked() {
+                    let path = std::path::Path::new(PRESETS_DIR).join(format!("{name}.ron"));
+                    match load_ui_state(&path) {
+                        Ok(loaded) => {
+                            *ui_state = loaded;
+                            preset_state.current_path = Some(path);
+                            preset_state.selected_preset = Some(name);
+                        }
+                        Err(err) => eprintln!("Failed to load preset: {err}"),
+                    }
+                }
+            }
         });
+}
 
+/// Applies `UiState::mat_transform` to the imported model every frame,
+/// independent of whether the Controls tab is open.
+fn apply_transform(
+    ui_state: Res<UiState>,
+    mut transformable: Query<(&mut Transform, &Transformable)>,
+) {
     for (mut transform, transformable) in &mut transformable {
         *transform = transformable.transform * Transform::from_matrix(ui_state.mat_transform);
     }
 }
+
+/// Bundles every resource a dock tab's contents need to draw themselves.
+/// Deriving `SystemParam` lets `ui_dock_area` build this once per frame and
+/// hand it to `egui_dock::DockArea` as the `TabViewer`.
+#[derive(SystemParam)]
+struct DockTabViewer<'w, 's> {
+    ui_state: ResMut<'w, UiState>,
+    timeline: ResMut<'w, Timeline>,
+    gamepad_state: ResMut<'w, GamepadState>,
+    preset_state: ResMut<'w, PresetState>,
+    ambient_light: ResMut<'w, AmbientLight>,
+    orbit_camera: Query<'w, 's, &'static mut OrbitCamera>,
+    time: Res<'w, Time>,
+    frame_count: Res<'w, FrameCount>,
+    egui_settings: Res<'w, EguiSettings>,
+    status: ResMut<'w, PgmStatus>,
+    loading: Option<Res<'w, AssetsLoading>>,
+    asset_server: Res<'w, AssetServer>,
+    commands: Commands<'w, 's>,
+}
+
+impl egui_dock::TabViewer for DockTabViewer<'_, '_> {
+    type Tab = Tab;
+
+    fn title(&mut self, tab: &mut Tab) -> egui::WidgetText {
+        tab.title().into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Tab) {
+        match tab {
+            Tab::Controls => controls_ui(
+                ui,
+                &mut self.ui_state,
+                &mut self.orbit_camera,
+                &mut self.preset_state,
+                &mut self.ambient_light,
+            ),
+            Tab::Status => status_ui(
+                ui,
+                &self.time,
+                &self.frame_count,
+                &self.egui_settings,
+                &mut self.status,
+                &self.loading,
+                &self.asset_server,
+                &mut self.commands,
+            ),
+            Tab::Help => help_ui(ui),
+            Tab::Timeline => timeline_ui(ui, &mut self.timeline, &self.ui_state),
+            Tab::GamepadBindings => gamepad_bindings_ui(ui, &mut self.gamepad_state),
+        }
+    }
+
+    fn closeable(&mut self, _tab: &mut Tab) -> bool {
+        true
+    }
+}
+
+/// Draws every dock tab inside a single `egui_dock::DockArea`, so panels can
+/// be split, tabbed together, or snapped to the viewport edges instead of
+/// floating free like the old per-window `egui::Window`s.
+fn ui_dock_area(
+    mut ctx: EguiContexts,
+    mut dock_layout: ResMut<DockLayout>,
+    mut viewer: DockTabViewer,
+) {
+    egui_dock::DockArea::new(&mut dock_layout.state)
+        .show_close_buttons(true)
+        .show(ctx.ctx_mut(), &mut viewer);
+}
+
+/// Saves the dock layout just before the app closes, so it's restored by
+/// `DockLayout::default` next launch the same way a preset would be.
+fn persist_dock_layout_on_exit(
+    mut exit_events: EventReader<AppExit>,
+    dock_layout: Res<DockLayout>,
+) {
+    if exit_events.iter().next().is_some() {
+        save_dock_layout(&dock_layout.state);
+    }
+}